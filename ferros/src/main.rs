@@ -1,15 +1,22 @@
 #![no_main]
 #![no_std]
 
-use core::panic::PanicInfo;
+mod panic_handler;
+mod smp;
+mod start;
 
 #[unsafe(naked)]
 #[unsafe(no_mangle)]
-pub extern "C" fn main() {
+pub extern "C" fn main(_dtb: u64) {
     core::arch::naked_asm!("1:", "   wfe", "   b 1b");
 }
 
-#[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    unimplemented!()
+/// Entry point for a secondary core released via [`smp::spin_up_core`].
+///
+/// The core's id is passed in `x0` by the spin-table trampoline in `start()`.
+#[unsafe(no_mangle)]
+pub extern "C" fn main_secondary(_core_id: usize) -> ! {
+    loop {
+        aarch64_cpu::asm::wfe();
+    }
 }
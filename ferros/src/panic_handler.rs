@@ -1,8 +1,126 @@
 use aarch64_cpu::asm;
+use core::fmt::{self, Write};
 use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Base address of the BCM2837 PL011 UART (UART0).
+const PL011_BASE: usize = 0x3F20_1000;
+/// Data register offset.
+const UART_DR: usize = 0x00;
+/// Flag register offset; bit 5 (`TXFF`) is set while the TX FIFO is full.
+const UART_FR: usize = 0x18;
+const UART_FR_TXFF: u32 = 1 << 5;
+
+/// Minimal blocking writer over the PL011 MMIO registers.
+///
+/// We talk to the hardware directly rather than going through an allocator so
+/// the handler keeps working even when the heap (or the rest of the kernel) is
+/// in an unknown state.
+struct Uart;
+
+impl Uart {
+    fn write_byte(&self, byte: u8) {
+        unsafe {
+            // spin while the transmit FIFO is full
+            while core::ptr::read_volatile((PL011_BASE + UART_FR) as *const u32) & UART_FR_TXFF != 0
+            {
+                asm::nop();
+            }
+            core::ptr::write_volatile((PL011_BASE + UART_DR) as *mut u32, byte as u32);
+        }
+    }
+}
+
+impl Write for Uart {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.write_byte(byte);
+        }
+        Ok(())
+    }
+}
+
+/// Guards against a panic raised while we are already reporting one; a second
+/// panic aborts to the halt loop instead of recursing on the UART.
+static PANICKING: AtomicBool = AtomicBool::new(false);
 
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
+fn panic(info: &PanicInfo) -> ! {
+    if PANICKING.swap(true, Ordering::SeqCst) {
+        // panic-within-panic: park deterministically without touching the UART
+        loop {
+            asm::wfe();
+        }
+    }
+
+    let mut uart = Uart;
+    match info.location() {
+        Some(loc) => {
+            let _ = write!(uart, "PANIC at {}:{}:{}: ", loc.file(), loc.line(), loc.column());
+        }
+        None => {
+            let _ = uart.write_str("PANIC at <unknown location>: ");
+        }
+    }
+    let _ = writeln!(uart, "{}", info.message());
+
+    terminate()
+}
+
+/// Final action once the panic has been reported.
+///
+/// The strategy is picked at compile time from the `panic-halt` /
+/// `panic-reset` Cargo features, mirroring the way upstream Rust splits
+/// `panic_abort` and `panic_unwind`. Exactly one branch is compiled in, so the
+/// unused strategy costs no code size and there is no runtime dispatch.
+///
+/// The two features are additive (Cargo has no mutual exclusion), so we reject
+/// enabling both or neither at compile time — exactly one strategy must win.
+#[cfg(all(feature = "panic-halt", feature = "panic-reset"))]
+compile_error!("enable exactly one of the `panic-halt` / `panic-reset` features");
+
+#[cfg(not(any(feature = "panic-halt", feature = "panic-reset")))]
+compile_error!("enable exactly one of the `panic-halt` / `panic-reset` features");
+
+#[cfg(all(feature = "panic-halt", not(feature = "panic-reset")))]
+fn terminate() -> ! {
+    // panic-halt: park the core forever.
+    loop {
+        asm::wfe();
+    }
+}
+
+/// Final action once the panic has been reported: trigger a full SoC reset via
+/// the BCM2837 power-management watchdog.
+#[cfg(feature = "panic-reset")]
+fn terminate() -> ! {
+    /// Magic value the PM block requires in the top byte of every write.
+    const PM_PASSWORD: u32 = 0x5a00_0000;
+    /// Reset control register.
+    const PM_RSTC: usize = 0x3F10_001c;
+    /// Watchdog register (timeout in ticks of the 16-bit watchdog clock).
+    const PM_WDOG: usize = 0x3F10_0024;
+    /// `WRCFG` field value requesting a full reset.
+    const PM_RSTC_WRCFG_FULL_RESET: u32 = 0x20;
+    /// Mask clearing the `WRCFG` field before we set it.
+    const PM_RSTC_WRCFG_CLR: u32 = 0xffff_ffcf;
+    /// Short countdown so the board resets promptly after the message flushes.
+    const TIMEOUT_TICKS: u32 = 10;
+    /// PL011 flag register bit 3 (`BUSY`): set while the UART is still transmitting.
+    const UART_FR_BUSY: u32 = 1 << 3;
+
+    unsafe {
+        // let the UART drain the panic message before arming the watchdog
+        while core::ptr::read_volatile((PL011_BASE + UART_FR) as *const u32) & UART_FR_BUSY != 0 {
+            asm::nop();
+        }
+        core::ptr::write_volatile(PM_WDOG as *mut u32, PM_PASSWORD | TIMEOUT_TICKS);
+        let rstc = core::ptr::read_volatile(PM_RSTC as *const u32);
+        let rstc = (rstc & PM_RSTC_WRCFG_CLR) | PM_RSTC_WRCFG_FULL_RESET;
+        core::ptr::write_volatile(PM_RSTC as *mut u32, PM_PASSWORD | rstc);
+    }
+
+    // Wait for the watchdog to fire.
     loop {
         asm::wfe();
     }
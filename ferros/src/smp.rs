@@ -0,0 +1,37 @@
+use aarch64_cpu::asm;
+use aarch64_cpu::asm::barrier;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of application cores on the BCM2837 (Cortex-A53 quad-core).
+pub const CORE_COUNT: usize = 4;
+
+/// Per-core spin-table release words.
+///
+/// Each parked core busy-waits on its own entry until core 0 writes a
+/// non-zero entry-point address and issues `sev`. The array lives in its own
+/// `.spin_table` section (see `kernel.ld`) so it is *not* cleared by the
+/// `.bss` zeroing loop in `start()` — otherwise a release published before the
+/// zeroing completed would be wiped out.
+#[unsafe(no_mangle)]
+#[unsafe(link_section = ".spin_table")]
+pub static CORE_RELEASE: [AtomicUsize; CORE_COUNT] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+/// Release a parked secondary core and hand it an entry point.
+///
+/// Writes `entry` into the core's spin-table slot and wakes every parked core
+/// with `sev`; the targeted core observes a non-zero release word, sets up its
+/// own stack and branches to `entry` (see the parking loop in `start()`). The
+/// entry point receives its core id in `x0`, matching `main_secondary`.
+pub fn spin_up_core(id: usize, entry: extern "C" fn(usize) -> !) {
+    CORE_RELEASE[id].store(entry as usize, Ordering::SeqCst);
+    // Ensure the release word is visible to the waking core before the event:
+    // it wakes from `wfe`, re-reads its slot once, and sleeps again with no
+    // further `sev` to re-arm it, so a stale read would strand the core.
+    barrier::dsb(barrier::ISH);
+    asm::sev();
+}
@@ -1,31 +1,89 @@
 use super::main;
+use core::sync::atomic::AtomicU64;
+
+/// Firmware boot arguments saved on entry.
+///
+/// The Raspberry Pi firmware passes the DTB pointer in `x0`; we stash it here
+/// before clobbering the register so it can be threaded into `main`. Lives in
+/// its own `.boot_args` section (outside `.bss`) so it survives the zeroing
+/// loop below.
+#[unsafe(no_mangle)]
+#[unsafe(link_section = ".boot_args")]
+pub static BOOT_ARGS: AtomicU64 = AtomicU64::new(0);
 
 #[unsafe(naked)]
 #[unsafe(no_mangle)]
 pub extern "C" fn start() {
     core::arch::naked_asm!(
+        // preserve the firmware boot args (DTB pointer in x0) before we clobber x0
+        "mov x19, x0",
         // check core ID, proceed only on core 0
         "mrs x0, MPIDR_EL1",
         "and x0, x0, 0b11",
         "cmp x0, 0",
-        "b.eq 2f", // if this is core 1, jump to stack pointer setup
-        // otherwise, fall into the infinite parking loop
+        "b.eq 2f", // if this is core 0, proceed with primary bring-up
+        // otherwise, wait on the spin table until core 0 releases us
         "1:",
         "wfe",
-        "b 1b",
-        // setup the stack pointer
+        "ldr x1, =CORE_RELEASE",
+        "ldr x2, [x1, x0, lsl #3]", // load this core's release word
+        "cbz x2, 1b",               // still zero: go back to sleep
+        // released: give this core its own stack (0x1FFF0000 - core_id*0x10000)
+        "mov x3, #0x10000",
+        "mul x3, x0, x3",
+        "mov x4, #0x1FFF0000",
+        "sub x4, x4, x3",
+        "mov sp, x4",
+        "br x2", // branch to the published entry point (core id stays in x0)
+        // core 0: record the DTB pointer
         "2:",
+        "ldr x9, =BOOT_ARGS",
+        "str x19, [x9]",
+        // drop to EL1 if the firmware entered us at EL2
+        "mrs x0, CurrentEL",
+        "lsr x0, x0, #2",
+        "cmp x0, #2",
+        "b.ne 7f", // already at EL1 (or below): skip the transition
+        // EL1 executes in AArch64
+        "mov x0, #(1 << 31)",
+        "msr HCR_EL2, x0",
+        // return to EL1h with DAIF masked (SPSR_EL2 = 0x3C5)
+        "mov x0, #0x3c5",
+        "msr SPSR_EL2, x0",
+        // resume at continue_in_el1 after the eret
+        "adr x0, 7f",
+        "msr ELR_EL2, x0",
+        // give EL1 its stack, then drop
+        "mov x0, #0x1FFF0000",
+        "msr SP_EL1, x0",
+        "eret",
+        // continue_in_el1
+        "7:",
         " mov sp, #0x1FFF0000",
         // zero the .bss section
         "ldr  x0, =bss_start",
         "ldr  x1, =bss_end",
-        "1:",
+        "3:",
         "cmp  x0, x1",
-        "b.eq 1f",
+        "b.eq 4f",
         "str  xzr, [x0], #8",
-        "b    1b",
-        // jump to Rust main!
-        "1:",
+        "b    3b",
+        // copy .data from its load address to its link-time address, word by word
+        // (a no-op while LMA == VMA; see the note on data_load in kernel.ld)
+        "4:",
+        "ldr  x0, =data_load",
+        "ldr  x1, =data_start",
+        "ldr  x2, =data_end",
+        "5:",
+        "cmp  x1, x2",
+        "b.eq 6f",
+        "ldr  x3, [x0], #8",
+        "str  x3, [x1], #8",
+        "b    5b",
+        // jump to Rust main with the saved DTB pointer in x0
+        "6:",
+        "ldr  x0, =BOOT_ARGS",
+        "ldr  x0, [x0]",
         "b {}", sym main
     );
 }